@@ -1,63 +1,248 @@
 use super::puzzle::Slot; // I don't love this dependency. Very open to other approaches.
+use std::collections::HashMap;
 use std::fmt;
 use std::fs::File;
 use std::io;
 use std::io::BufRead; // required for BufReader::lines()??? I don't get.
 use std::path::Path;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A fixed-size bitset over word indices, backed by `u64` words.
+///
+/// This backs the per-length, per-(position, letter) index in [`LengthIndex`]: each
+/// bit marks whether a given word is still a candidate.
+#[derive(Clone, Debug)]
+struct Bitset {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Bitset {
+    fn zeros(len: usize) -> Self {
+        Self {
+            words: vec![0u64; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    fn ones(len: usize) -> Self {
+        let mut bitset = Self::zeros(len);
+        for index in 0..len {
+            bitset.set(index);
+        }
+        bitset
+    }
+
+    fn set(&mut self, index: usize) {
+        self.words[index / 64] |= 1u64 << (index % 64);
+    }
+
+    fn and_with(&mut self, other: &Bitset) {
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a &= b;
+        }
+    }
+
+    fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        let len = self.len;
+        self.words.iter().enumerate().flat_map(move |(word_idx, &word)| {
+            (0..64).filter_map(move |bit| {
+                let index = word_idx * 64 + bit;
+                (index < len && (word >> bit) & 1 == 1).then_some(index)
+            })
+        })
+    }
+}
+
+/// Per-length inverted index: for every `(position, letter)` pair, which words (by
+/// index into the matching `words_by_length` bucket) have that letter at that
+/// position.
+#[derive(Clone, Debug)]
+struct LengthIndex {
+    position_letter: Vec<HashMap<String, Bitset>>,
+    word_count: usize,
+}
+
+impl LengthIndex {
+    fn build(words: &[String], length: usize) -> Self {
+        let mut position_letter = vec![HashMap::new(); length];
+        for (word_idx, word) in words.iter().enumerate() {
+            for (pos, cluster) in word.graphemes(true).enumerate() {
+                position_letter[pos]
+                    .entry(cluster.to_string())
+                    .or_insert_with(|| Bitset::zeros(words.len()))
+                    .set(word_idx);
+            }
+        }
+        Self {
+            position_letter,
+            word_count: words.len(),
+        }
+    }
+
+    /// Indices (into the matching `words_by_length` bucket) of words consistent
+    /// with the slot's currently-filled squares.
+    fn matching_indices(&self, slot: &Slot) -> Vec<usize> {
+        if slot.len() != self.position_letter.len() {
+            return vec![]; // no word of this length is in the lexicon
+        }
+        let mut candidates = Bitset::ones(self.word_count);
+        for pos in 0..slot.len() {
+            let Some(cluster) = slot.get(pos) else {
+                continue; // an unfilled square matches anything
+            };
+            match self.position_letter[pos].get(&cluster.to_uppercase()) {
+                Some(bitset) => candidates.and_with(bitset),
+                None => return vec![],
+            }
+        }
+        candidates.iter_ones().collect()
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Lexicon {
-    // If we are enforcing ascii, I assume there's a better way to do this than using Strings.
+    // bucketed by grapheme-cluster count, so non-ascii words land in the right bucket
     words: Vec<Vec<String>>,
+    // parallel to `words`: `scores[length][i]` is the score of `words[length][i]`
+    scores: Vec<Vec<i32>>,
+    indices: Vec<LengthIndex>,
     empty_set: Vec<String>, // used for word lengths that aren't in the lexicon
+    empty_scores: Vec<i32>,
+    empty_index: LengthIndex,
+}
+
+/// Accumulates words (with scores) into a [`Lexicon`], one at a time, so a lexicon can
+/// be built from any source (a file, stdin, multiple merged sources, ...) without
+/// collecting every word into an intermediate `Vec` first.
+#[derive(Clone, Debug, Default)]
+pub struct LexiconBuilder {
+    words: Vec<Vec<String>>,
+    scores: Vec<Vec<i32>>,
+}
+
+impl LexiconBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a single word (with its score) straight into its length bucket.
+    pub fn add_word(&mut self, word: &str, score: i32) {
+        let word = word.to_uppercase();
+        let length = word.graphemes(true).count();
+        if length >= self.words.len() {
+            self.words.resize_with(length + 1, Vec::new);
+            self.scores.resize_with(length + 1, Vec::new);
+        }
+        self.words[length].push(word);
+        self.scores[length].push(score);
+    }
+
+    /// Adds every `(word, score)` pair from `words`.
+    pub fn extend<I: IntoIterator<Item = (String, i32)>>(&mut self, words: I) {
+        for (word, score) in words {
+            self.add_word(&word, score);
+        }
+    }
+
+    /// Builds the [`Lexicon`], computing its bitset index once over the accumulated
+    /// words.
+    pub fn build(self) -> Lexicon {
+        let indices = self
+            .words
+            .iter()
+            .enumerate()
+            .map(|(length, words)| LengthIndex::build(words, length))
+            .collect();
+
+        Lexicon {
+            words: self.words,
+            scores: self.scores,
+            indices,
+            empty_set: vec![],
+            empty_scores: vec![],
+            empty_index: LengthIndex::build(&[], 0),
+        }
+    }
 }
 
 //constructors
 impl Lexicon {
     /// Empty Lexicon
     pub fn empty() -> Self {
-        Self {
-            words: vec![vec![]],
-            empty_set: vec![],
-        }
+        Self::builder().build()
+    }
+
+    /// Starts accumulating a lexicon from multiple sources; see [`LexiconBuilder`].
+    pub fn builder() -> LexiconBuilder {
+        LexiconBuilder::new()
+    }
+
+    /// Lexicon from a list of `(word, score)` pairs. Higher scores are preferred by
+    /// [`Lexicon::possible_answers_ranked`].
+    pub fn from_scored_words(words: Vec<(String, i32)>) -> Self {
+        let mut builder = Self::builder();
+        builder.extend(words);
+        builder.build()
     }
 
     /// Lexicon from a list of words
     ///
-    /// Will silently ignore non-ascii words.
+    /// Words are bucketed by grapheme-cluster count (not byte length), so
+    /// non-ascii words work the same as ascii ones. Every word gets the same
+    /// default score (`0`); use [`Lexicon::from_scored_words`] to rank words.
     pub fn from_words(words: Vec<String>) -> Self {
-        // TODO what's the right way to generalize this to unicode? Do we even want to do that?
-        // get max word length
-        let max_length = words.iter().map(|word| word.len()).max().unwrap_or(0);
-
-        // fill the set for each length
-        let mut words_by_length = vec![vec![]; max_length + 1];
-        for word in words {
-            if !word.chars().all(|c| c.is_ascii()) {
-                continue;
-            }
-            let word = word.to_ascii_uppercase();
-
-            words_by_length[word.len()].push(word);
-        }
+        Self::from_scored_words(words.into_iter().map(|word| (word, 0)).collect())
+    }
 
-        Self {
-            words: words_by_length,
-            empty_set: vec![],
+    /// Lexicon from any line-oriented reader, one plain word per line.
+    ///
+    /// Unlike building a `Vec<String>` first, this streams lines straight into their
+    /// length buckets, so memory stays proportional to the words actually kept (not
+    /// words-plus-raw-line-buffer) -- useful for feeding a lexicon from stdin, a
+    /// decompressing reader, or anything else that isn't a plain file.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, io::Error> {
+        let mut builder = Self::builder();
+        for line in reader.lines() {
+            builder.add_word(&line?, 0);
         }
+        Ok(builder.build())
     }
 
-    /// Lexicon from a file.
-    ///
-    /// Reads the file contents to memory and passes to `Lexicon::from_words`.
+    /// Lexicon from a file, one plain word per line.
     pub fn from_file<P: AsRef<Path>>(filename: P) -> Result<Self, io::Error> {
         let file = File::open(filename)?;
+        Self::from_reader(io::BufReader::new(file))
+    }
 
-        // I don't love reading everything into memory, but had a hard time doing anything
-        // smarter. the lexicon has to fit in memory unless we get fancy, so this isn't
-        // going to be too big.
-        let words = io::BufReader::new(file).lines().collect::<Result<_, _>>()?;
-        Ok(Self::from_words(words))
+    /// Lexicon from any line-oriented reader in `WORD;score` format, one entry per
+    /// line. A line with no `;` delimiter falls back to `default_score`, so plain
+    /// wordlists (the format [`Lexicon::from_reader`] expects) still load fine here.
+    pub fn from_scored_reader<R: BufRead>(
+        reader: R,
+        default_score: i32,
+    ) -> Result<Self, io::Error> {
+        let mut builder = Self::builder();
+        for line in reader.lines() {
+            let line = line?;
+            match line.split_once(';') {
+                Some((word, score)) => {
+                    let score = score.trim().parse().unwrap_or(default_score);
+                    builder.add_word(word, score);
+                }
+                None => builder.add_word(&line, default_score),
+            }
+        }
+        Ok(builder.build())
+    }
+
+    /// Lexicon from a file in `WORD;score` format; see [`Lexicon::from_scored_reader`].
+    pub fn from_scored_file<P: AsRef<Path>>(
+        filename: P,
+        default_score: i32,
+    ) -> Result<Self, io::Error> {
+        let file = File::open(filename)?;
+        Self::from_scored_reader(io::BufReader::new(file), default_score)
     }
 }
 
@@ -68,6 +253,11 @@ impl Lexicon {
         self.words.iter().map(|w| w.len()).sum()
     }
 
+    /// Whether the lexicon holds no words
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// HashSet of words of a given length
     pub fn words_by_length(&self, length: usize) -> &Vec<String> {
         if length >= self.words.len() {
@@ -77,26 +267,51 @@ impl Lexicon {
         }
     }
 
+    /// Scores of words of a given length, parallel to `words_by_length(length)`
+    fn scores_by_length(&self, length: usize) -> &Vec<i32> {
+        if length >= self.scores.len() {
+            &self.empty_scores
+        } else {
+            &self.scores[length]
+        }
+    }
+
+    fn index_for(&self, length: usize) -> &LengthIndex {
+        if length >= self.indices.len() {
+            &self.empty_index
+        } else {
+            &self.indices[length]
+        }
+    }
+
+    /// Indices (into `words_by_length(slot.len())`) of words consistent with a slot's
+    /// currently-filled squares, without materializing any `String`s.
+    pub fn possible_answer_indices(&self, slot: &Slot) -> Vec<usize> {
+        self.index_for(slot.len()).matching_indices(slot)
+    }
+
     /// Possible answers for a given slot
     pub fn possible_answers(&self, slot: &Slot) -> Vec<String> {
-        // should this be an iterator instead of a vector?
-        let mut answers = vec![];
-        for word in self.words_by_length(slot.len()) {
-            let mut matches = true;
-
-            for (i, c) in word.chars().enumerate() {
-                // THIS ASSUMES UNFILLED SQUARE ARE REPRESENTED BY A SPACE
-                if slot[i] != ' ' && slot[i].to_ascii_uppercase() != c {
-                    matches = false;
-                    break;
-                }
-            }
+        let words = self.words_by_length(slot.len());
+        self.possible_answer_indices(slot)
+            .into_iter()
+            .map(|i| words[i].clone())
+            .collect()
+    }
 
-            if matches {
-                answers.push(word.clone());
-            }
-        }
-        answers
+    /// Possible answers for a slot, as `(word, score)` pairs sorted by descending
+    /// score, excluding any candidate scoring below `min_score`.
+    pub fn possible_answers_ranked(&self, slot: &Slot, min_score: i32) -> Vec<(String, i32)> {
+        let words = self.words_by_length(slot.len());
+        let scores = self.scores_by_length(slot.len());
+        let mut ranked: Vec<(String, i32)> = self
+            .possible_answer_indices(slot)
+            .into_iter()
+            .map(|i| (words[i].clone(), scores[i]))
+            .filter(|&(_, score)| score >= min_score)
+            .collect();
+        ranked.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        ranked
     }
 }
 
@@ -155,7 +370,7 @@ cat.
 xy .
 c  s\
 ";
-        let puzzle = Puzzle::from_str(crossword_s).unwrap();
+        let puzzle = Puzzle::parse(crossword_s).unwrap();
 
         // Test exact match
         println!("slot 0: {}", String::from(puzzle.access(0)));
@@ -171,4 +386,94 @@ c  s\
         // Test too-long slot
         assert!(lexicon.possible_answers(&puzzle.access(3)).is_empty());
     }
+
+    #[test]
+    fn test_possible_answer_indices_match_possible_answers() {
+        let words = vec!["cat".to_string(), "rat".to_string(), "bat".to_string()];
+        let lexicon = Lexicon::from_words(words);
+        let puzzle = Puzzle::parse(" at").unwrap();
+
+        let by_index: Vec<String> = lexicon
+            .possible_answer_indices(&puzzle.access(0))
+            .into_iter()
+            .map(|i| lexicon.words_by_length(3)[i].clone())
+            .collect();
+        let mut by_index_sorted = by_index.clone();
+        by_index_sorted.sort();
+        let mut by_string_sorted = lexicon.possible_answers(&puzzle.access(0));
+        by_string_sorted.sort();
+        assert_eq!(by_index_sorted, by_string_sorted);
+    }
+
+    #[test]
+    fn test_possible_answers_ranked() {
+        let lexicon = Lexicon::from_scored_words(vec![
+            ("cat".to_string(), 10),
+            ("rat".to_string(), 50),
+            ("bat".to_string(), 1),
+        ]);
+        let puzzle = Puzzle::parse(" at").unwrap();
+
+        let ranked = lexicon.possible_answers_ranked(&puzzle.access(0), i32::MIN);
+        assert_eq!(
+            ranked,
+            vec![
+                ("RAT".to_string(), 50),
+                ("CAT".to_string(), 10),
+                ("BAT".to_string(), 1),
+            ]
+        );
+
+        // a cutoff filters out the low scorers
+        let cutoff = lexicon.possible_answers_ranked(&puzzle.access(0), 10);
+        assert_eq!(
+            cutoff,
+            vec![("RAT".to_string(), 50), ("CAT".to_string(), 10)]
+        );
+    }
+
+    #[test]
+    fn test_from_scored_file() {
+        let path = std::env::temp_dir().join("test_scored_words.txt");
+        std::fs::write(&path, "cat;10\nrat;50\ndog").unwrap();
+        let lexicon = Lexicon::from_scored_file(&path, 0).unwrap();
+
+        let puzzle = Puzzle::parse(" at").unwrap();
+        let ranked = lexicon.possible_answers_ranked(&puzzle.access(0), i32::MIN);
+        assert_eq!(
+            ranked,
+            vec![("RAT".to_string(), 50), ("CAT".to_string(), 10)]
+        );
+        // "dog" had no score delimiter, so it fell back to the default score
+        assert!(lexicon.words_by_length(3).contains(&"DOG".to_string()));
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let lexicon = Lexicon::from_reader(std::io::Cursor::new("cat\ndog\nbear")).unwrap();
+        assert_eq!(lexicon.len(), 3);
+        assert_eq!(lexicon.words_by_length(3).len(), 2);
+        assert_eq!(lexicon.words_by_length(4).len(), 1);
+    }
+
+    #[test]
+    fn test_builder_merges_multiple_sources() {
+        let mut builder = Lexicon::builder();
+        builder.extend(vec![("cat".to_string(), 10)]);
+        builder.extend(vec![("rat".to_string(), 50)]);
+        builder.add_word("bat", 1);
+        let lexicon = builder.build();
+
+        assert_eq!(lexicon.len(), 3);
+        let puzzle = Puzzle::parse(" at").unwrap();
+        let ranked = lexicon.possible_answers_ranked(&puzzle.access(0), i32::MIN);
+        assert_eq!(
+            ranked,
+            vec![
+                ("RAT".to_string(), 50),
+                ("CAT".to_string(), 10),
+                ("BAT".to_string(), 1),
+            ]
+        );
+    }
 }
@@ -2,13 +2,34 @@
 
 use ndarray::prelude::*;
 use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
+use std::collections::HashMap;
 use std::convert::From;
 use std::fmt;
 use std::ops::{Index, Range};
 use std::string::String;
 
-type Square = Option<char>;
+/// Splits `s` into its extended grapheme clusters.
+///
+/// This is the one place that decides how "one user-perceived character" maps onto
+/// Unicode scalars, so swapping segmentation backends (e.g. an ICU4X-backed one for
+/// locale-tailored boundaries, instead of unicode-segmentation's default UAX#29
+/// algorithm) only means changing this function. This crate has no `Cargo.toml` yet to
+/// declare an alternate backend behind a feature flag, so for now there's only the one
+/// implementation.
+fn graphemes(s: &str) -> impl Iterator<Item = &str> {
+    s.graphemes(true)
+}
+
+/// A single grid cell.
+///
+/// `None` means the cell currently holds no grapheme cluster: either it's a
+/// block (see [`Puzzle`]'s `blocks` mask) or it's a fillable square that
+/// hasn't been solved yet. `Some` holds the one user-perceived character
+/// (i.e. [grapheme cluster](https://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundaries))
+/// occupying the cell.
+type Square = Option<Box<str>>;
 
 /// A read-only view of a crossword slot
 ///
@@ -26,14 +47,20 @@ impl Slot<'_> {
     pub fn is_empty(&self) -> bool {
         self.view.is_empty()
     }
+
+    /// Returns the grapheme cluster at `index`, or `None` if the square
+    /// hasn't been filled in yet.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.view[index].as_deref()
+    }
 }
 
 impl Index<usize> for Slot<'_> {
-    type Output = char;
+    type Output = str;
 
     fn index(&self, index: usize) -> &Self::Output {
-        if let Some(ref letter) = self.view[index] {
-            letter
+        if let Some(ref cluster) = self.view[index] {
+            cluster
         } else {
             panic!("A slot instance should not hold an empty Square");
         }
@@ -42,13 +69,15 @@ impl Index<usize> for Slot<'_> {
 
 impl From<Slot<'_>> for String {
     fn from(item: Slot) -> String {
-        String::from_iter(item.view.iter().map(|elem| -> char {
-            if let Some(chr) = elem {
-                *chr
+        let mut out = String::new();
+        for elem in item.view.iter() {
+            if let Some(cluster) = elem {
+                out.push_str(cluster);
             } else {
                 panic!("A slot instance should not hold an empty Square");
             }
-        }))
+        }
+        out
     }
 }
 
@@ -57,6 +86,29 @@ impl From<Slot<'_>> for String {
 struct SlotCoords {
     r: Range<usize>, // starting and stopping coordinate along slice axis
     k: usize,        // row / col the slot is in
+    // clue number this slot starts at; 0 for a single-cell slot, which is never a
+    // numbered entry in its own direction (even if its cell starts one the other way)
+    number: usize,
+}
+
+/// Which way a slot runs through the grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Across,
+    Down,
+}
+
+/// A grid cell where an across slot and a down slot intersect.
+///
+/// `across`/`down` are slot indices, usable with [`Puzzle::access`] just like any
+/// other slot index; `across_offset`/`down_offset` are the position within each slot
+/// of the shared square.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Crossing {
+    pub across: usize,
+    pub down: usize,
+    pub across_offset: usize,
+    pub down_offset: usize,
 }
 
 /// Puzzle grid state.
@@ -65,17 +117,24 @@ struct SlotCoords {
 #[derive(Clone, Debug)]
 pub struct Puzzle {
     grid: Array2<Square>,
+    // true for cells that are blocks (outside any slot); fixed at construction time and
+    // never touched by `with_filled_slot`.
+    blocks: Array2<bool>,
     downs: Vec<SlotCoords>,
     acrosses: Vec<SlotCoords>,
+    crossings: Vec<Crossing>,
+    // crossings touching slot `i`, indexed the same way as `access`; each crossing
+    // that involves slot `i` appears once here, copied out of `crossings`
+    crossings_by_slot: Vec<Vec<Crossing>>,
 }
 
 impl Puzzle {
-    fn identify_slots(grid: &Array2<Square>) -> (Vec<SlotCoords>, Vec<SlotCoords>) {
+    fn identify_slots(blocks: &Array2<bool>) -> (Vec<SlotCoords>, Vec<SlotCoords>) {
         let mut downs = vec![];
         let mut acrosses = vec![];
 
         // first downs, then acrosses
-        for (rows_or_cols, slots) in [(grid.columns(), &mut downs), (grid.rows(), &mut acrosses)] {
+        for (rows_or_cols, slots) in [(blocks.columns(), &mut downs), (blocks.rows(), &mut acrosses)] {
             // first iterate over top row
             for (k, rowcol) in rows_or_cols.into_iter().enumerate() {
                 let mut stop = 0usize; // cursor for slot end position
@@ -85,18 +144,18 @@ impl Puzzle {
                     let mut start = stop; // cursor for slot start position
 
                     // find the first fillable square
-                    while start < rowcol.len() && rowcol[start].is_none() {
+                    while start < rowcol.len() && rowcol[start] {
                         start += 1;
                     }
 
                     // find the last following fillable square
                     stop = start;
-                    while stop < rowcol.len() && !rowcol[stop].is_none() {
+                    while stop < rowcol.len() && !rowcol[stop] {
                         stop += 1;
                     }
 
                     if start != stop {
-                        slots.push(SlotCoords { r: start..stop, k });
+                        slots.push(SlotCoords { r: start..stop, k, number: 0 });
                     }
                     stop += 1;
                 }
@@ -105,48 +164,110 @@ impl Puzzle {
         (acrosses, downs)
     }
 
-    /// Construct a Puzzle from a string view
+    /// Assigns clue numbers to `acrosses` and `downs` in place.
     ///
-    /// #Note About Grapheme Clusters
-    /// [grapheme clusters](https://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundaries)
-    /// are a subtle aspect of unicode.
-    /// - in short, a "user-perceived character" may correspond to a cluster
-    ///   of one or more unicode characters.
-    /// - As I understand it, you can think of most of these characters as
-    ///   "modifiers" (I believe a g with grave-accent is a "g" followed by a
-    ///   grave-modifier character). BE AWARE: This mental model may not apply
-    ///   for some characters used to represent non-latin-alphabet languages.
-    /// - In any case, a grapheme cluster is an approximation for these
-    ///   clusters of letters
+    /// Scans the grid in row-major order; a fill cell starts an across entry when its
+    /// left neighbor is a block (or the grid edge) and its right neighbor is a fill
+    /// cell, and starts a down entry when its neighbor above is a block/edge and its
+    /// neighbor below is a fill cell. Each cell that starts at least one entry gets the
+    /// next incrementing number, shared between across and down if it starts both.
+    fn number_slots(blocks: &Array2<bool>, acrosses: &mut [SlotCoords], downs: &mut [SlotCoords]) {
+        let across_starts: HashMap<(usize, usize), usize> = acrosses
+            .iter()
+            .enumerate()
+            .map(|(idx, coords)| ((coords.k, coords.r.start), idx))
+            .collect();
+        let down_starts: HashMap<(usize, usize), usize> = downs
+            .iter()
+            .enumerate()
+            .map(|(idx, coords)| ((coords.k, coords.r.start), idx))
+            .collect();
+
+        let (nrows, ncols) = blocks.dim();
+        let mut number = 0usize;
+        for i in 0..nrows {
+            for j in 0..ncols {
+                if blocks[[i, j]] {
+                    continue;
+                }
+                let starts_across =
+                    (j == 0 || blocks[[i, j - 1]]) && j + 1 < ncols && !blocks[[i, j + 1]];
+                let starts_down =
+                    (i == 0 || blocks[[i - 1, j]]) && i + 1 < nrows && !blocks[[i + 1, j]];
+                if !starts_across && !starts_down {
+                    continue;
+                }
+
+                number += 1;
+                if starts_across {
+                    acrosses[across_starts[&(i, j)]].number = number;
+                }
+                if starts_down {
+                    downs[down_starts[&(j, i)]].number = number;
+                }
+            }
+        }
+    }
+
+    /// Computes every across/down intersection, derived purely from `SlotCoords`: a
+    /// down slot at column `k` spanning rows `r` crosses an across slot at row `k2`
+    /// spanning cols `r2` exactly when `k2 ∈ r` and `k ∈ r2`.
+    fn compute_crossings(acrosses: &[SlotCoords], downs: &[SlotCoords]) -> Vec<Crossing> {
+        let mut crossings = vec![];
+        for (across_idx, across) in acrosses.iter().enumerate() {
+            for (down_idx, down) in downs.iter().enumerate() {
+                if down.r.contains(&across.k) && across.r.contains(&down.k) {
+                    crossings.push(Crossing {
+                        across: across_idx,
+                        down: acrosses.len() + down_idx,
+                        across_offset: down.k - across.r.start,
+                        down_offset: across.k - down.r.start,
+                    });
+                }
+            }
+        }
+        crossings
+    }
+
+    /// Groups `crossings` by the slot indices they touch, so `crossings_of` can hand
+    /// back a plain slice.
+    fn group_crossings_by_slot(nslots: usize, crossings: &[Crossing]) -> Vec<Vec<Crossing>> {
+        let mut by_slot = vec![vec![]; nslots];
+        for crossing in crossings {
+            by_slot[crossing.across].push(*crossing);
+            by_slot[crossing.down].push(*crossing);
+        }
+        by_slot
+    }
+
+    /// Construct a Puzzle from a string view
     ///
-    /// In the future, a crossword puzzle should fully support arbitrary
-    /// grapheme clusters. For now, this constructor, will parse the cluster,
-    /// and report an error.
+    /// Each row is segmented into [grapheme
+    /// clusters](https://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundaries)
+    /// (extended, not legacy) so that a single user-perceived character that
+    /// spans more than one Unicode scalar (e.g. `e` followed by a combining
+    /// accent) still occupies exactly one grid cell. `'.'` marks a block, a
+    /// single space marks a fillable square that hasn't been solved yet, and
+    /// any other grapheme cluster is the cell's initial contents.
     pub fn parse(s: &str) -> Result<Puzzle, &'static str> {
         let v: Vec<&str> = s.split('\n').collect();
-        // true to use extended, as opposed to legacy grapheme clusters
-        let ncols = v[0].graphemes(true).count();
+        let ncols = graphemes(v[0]).count();
         let nrows = v.len();
-        let mut grid = Array::from_elem((nrows, ncols), None);
+        let mut grid: Array2<Square> = Array::from_elem((nrows, ncols), None);
+        let mut blocks = Array::from_elem((nrows, ncols), false);
 
         for i in 0..nrows {
             let mut j = 0;
-            for grapheme in UnicodeSegmentation::graphemes(v[i], true) {
+            for grapheme in graphemes(v[i]) {
                 if j == ncols {
                     // with custom error types, we coud be more descriptive
                     return Err("a row has too many characters");
                 }
 
-                let mut inner_it = grapheme.chars();
-                // based on my understanding of invariants, the following never panics!
-                let chr = inner_it.next().unwrap();
-                if let Some(_dummy) = inner_it.next() {
-                    return Err("crossword puzzle can't contain a grapheme cluster composed of more than 1 unicode character");
-                } else {
-                    grid[[i, j]] = match chr {
-                        '.' => None,
-                        other => Some(other),
-                    }
+                match grapheme {
+                    "." => blocks[[i, j]] = true,
+                    " " => {} // fillable, but not yet solved
+                    other => grid[[i, j]] = Some(Box::from(other)),
                 }
                 j += 1;
             }
@@ -155,11 +276,18 @@ impl Puzzle {
             }
         }
 
-        let (acrosses, downs) = Puzzle::identify_slots(&grid);
+        let (mut acrosses, mut downs) = Puzzle::identify_slots(&blocks);
+        Puzzle::number_slots(&blocks, &mut acrosses, &mut downs);
+        let crossings = Puzzle::compute_crossings(&acrosses, &downs);
+        let crossings_by_slot =
+            Puzzle::group_crossings_by_slot(acrosses.len() + downs.len(), &crossings);
         Ok(Puzzle {
             grid,
+            blocks,
             acrosses,
             downs,
+            crossings,
+            crossings_by_slot,
         })
     }
 
@@ -194,30 +322,299 @@ impl Puzzle {
         }
     }
 
+    /// The clue number and direction of slot `i`, as assigned by [`Puzzle::parse`]'s
+    /// numbering pass. A single-cell slot is not a numbered entry in its direction and
+    /// has number `0`, even when its cell begins an entry in the other direction.
+    pub fn slot_number(&self, i: usize) -> (usize, Direction) {
+        if i < self.nacross() {
+            (self.acrosses[i].number, Direction::Across)
+        } else if i < self.nslots() {
+            (self.downs[i - self.nacross()].number, Direction::Down)
+        } else {
+            panic!("The index is too large!");
+        }
+    }
+
+    /// Every slot as `(number, direction, Slot)`, ordered the way a printed clue list
+    /// is: increasing number, across before down when a cell starts both.
+    pub fn numbered_slots(&self) -> impl Iterator<Item = (usize, Direction, Slot<'_>)> {
+        let mut order: Vec<usize> = (0..self.nslots()).collect();
+        order.sort_by_key(|&i| {
+            let (number, direction) = self.slot_number(i);
+            (number, direction != Direction::Across)
+        });
+        order
+            .into_iter()
+            .map(move |i| {
+                let (number, direction) = self.slot_number(i);
+                (number, direction, self.access(i))
+            })
+    }
+
+    /// Every across/down intersection in the grid.
+    pub fn crossings(&self) -> Vec<Crossing> {
+        self.crossings.clone()
+    }
+
+    /// The crossings that touch slot `i`, one entry per slot it crosses.
+    pub fn crossings_of(&self, i: usize) -> &[Crossing] {
+        &self.crossings_by_slot[i]
+    }
+
+    /// Whether every crossing cell agrees between its across slot and its down slot.
+    ///
+    /// Since both slots are views over the same underlying grid, this is always true
+    /// for a `Puzzle` built by this module; it's a sanity check for anything that
+    /// might construct crossings independently of the grid (e.g. a solver working
+    /// with its own copy of the candidate letters).
+    pub fn is_consistent(&self) -> bool {
+        self.crossings.iter().all(|crossing| {
+            self.access(crossing.across).get(crossing.across_offset)
+                == self.access(crossing.down).get(crossing.down_offset)
+        })
+    }
+
     /// creates a new copy with a filled in puzzle
     ///
     /// This interface provides the desired CoW semantics (even if we don't
     /// currently take advantage of them)
     pub fn with_filled_slot(&self, i: usize, value: &str) -> Self {
         let mut copy = self.clone();
-        // this could be more efficient
-        if value.chars().count() != copy.access(i).len() {
+        copy.fill_slot_mut(i, value);
+        copy
+    }
+
+    /// Fills a slot in place, without cloning the rest of the grid.
+    ///
+    /// Meant for hot paths (e.g. backtracking search) that would otherwise pay for a
+    /// full grid clone at every node; see [`Puzzle::with_filled_slot`] for the CoW
+    /// variant.
+    pub(crate) fn fill_slot_mut(&mut self, i: usize, value: &str) {
+        if graphemes(value).count() != self.access(i).len() {
             panic!("value doesn't have the correct length");
         }
 
         let mut view = if i < self.nacross() {
-            let coords: &SlotCoords = &copy.acrosses[i];
-            copy.grid.slice_mut(s![coords.k, coords.r.clone()])
+            let coords: &SlotCoords = &self.acrosses[i];
+            self.grid.slice_mut(s![coords.k, coords.r.clone()])
         } else {
             let coords: &SlotCoords = &self.downs[i - self.nacross()];
-            copy.grid.slice_mut(s![coords.r.clone(), coords.k])
+            self.grid.slice_mut(s![coords.r.clone(), coords.k])
         };
 
-        for (j, chr) in value.char_indices() {
-            view[j] = Some(chr);
+        for (j, cluster) in graphemes(value).enumerate() {
+            view[j] = Some(Box::from(cluster));
+        }
+    }
+
+    /// Snapshot of a slot's current squares, usable to undo a [`Puzzle::fill_slot_mut`]
+    /// via [`Puzzle::restore_slot_mut`].
+    pub(crate) fn slot_contents(&self, i: usize) -> Vec<Square> {
+        self.access(i).view.iter().cloned().collect()
+    }
+
+    /// Restores a slot's squares from a snapshot taken by [`Puzzle::slot_contents`].
+    pub(crate) fn restore_slot_mut(&mut self, i: usize, values: &[Square]) {
+        let mut view = if i < self.nacross() {
+            let coords: &SlotCoords = &self.acrosses[i];
+            self.grid.slice_mut(s![coords.k, coords.r.clone()])
+        } else {
+            let coords: &SlotCoords = &self.downs[i - self.nacross()];
+            self.grid.slice_mut(s![coords.r.clone(), coords.k])
+        };
+        for (j, value) in values.iter().enumerate() {
+            view[j] = value.clone();
         }
-        copy
     }
+
+    /// Maps a position within slot `i` (an offset into that slot) to the crossing
+    /// slot that shares that grid square, and the offset within *that* slot.
+    ///
+    /// Every square belongs to exactly one across slot and one down slot, so this
+    /// always finds a match for a valid `(i, offset)` pair.
+    pub(crate) fn crossing(&self, i: usize, offset: usize) -> (usize, usize) {
+        assert!(offset < self.access(i).len(), "offset out of range for slot");
+
+        if i < self.nacross() {
+            let coords = &self.acrosses[i];
+            let row = coords.k;
+            let col = coords.r.start + offset;
+            let (j, down) = self
+                .downs
+                .iter()
+                .enumerate()
+                .find(|(_, down)| down.k == col && down.r.contains(&row))
+                .expect("every square belongs to some down slot");
+            (self.nacross() + j, row - down.r.start)
+        } else {
+            let coords = &self.downs[i - self.nacross()];
+            let col = coords.k;
+            let row = coords.r.start + offset;
+            let (j, across) = self
+                .acrosses
+                .iter()
+                .enumerate()
+                .find(|(_, across)| across.k == row && across.r.contains(&col))
+                .expect("every square belongs to some across slot");
+            (j, col - across.r.start)
+        }
+    }
+
+    /// Maps every cell that starts a numbered entry to its clue number.
+    fn cell_numbers(&self) -> HashMap<(usize, usize), usize> {
+        let mut numbers = HashMap::new();
+        for coords in &self.acrosses {
+            if coords.number != 0 {
+                numbers.insert((coords.k, coords.r.start), coords.number);
+            }
+        }
+        for coords in &self.downs {
+            if coords.number != 0 {
+                numbers.insert((coords.r.start, coords.k), coords.number);
+            }
+        }
+        numbers
+    }
+
+    /// Renders the grid as a bordered box-drawing diagram, with each starting cell's
+    /// clue number in a small sub-row above its contents.
+    ///
+    /// Every cell is padded to the same column width, wide enough for the widest
+    /// grapheme cluster or clue number so a double-width (e.g. CJK) cluster fills the
+    /// whole cell while narrower ones are padded to match; block squares render as a
+    /// solid fill.
+    pub fn render_grid(&self, style: GridStyle) -> String {
+        let chars = style.box_chars();
+        let (nrows, ncols) = self.grid.dim();
+        let numbers = self.cell_numbers();
+
+        let label_width = numbers.values().map(|n| n.to_string().len()).max().unwrap_or(0);
+        let square_width = self
+            .grid
+            .iter()
+            .filter_map(|square| square.as_deref())
+            .map(UnicodeWidthStr::width)
+            .max()
+            .unwrap_or(1);
+        let cell_width = label_width.max(square_width).max(1);
+
+        let border = |left: char, mid: char, right: char| -> String {
+            let mut line = String::new();
+            line.push(left);
+            for j in 0..ncols {
+                line.push_str(&chars.horizontal.to_string().repeat(cell_width));
+                line.push(if j + 1 < ncols { mid } else { right });
+            }
+            line.push('\n');
+            line
+        };
+
+        let mut out = String::new();
+        out.push_str(&border(chars.top_left, chars.top_mid, chars.top_right));
+        for i in 0..nrows {
+            out.push(chars.vertical);
+            for j in 0..ncols {
+                let label = numbers.get(&(i, j)).map_or(String::new(), |n| n.to_string());
+                out.push_str(&self.render_cell(i, j, &label, cell_width, &chars));
+                out.push(chars.vertical);
+            }
+            out.push('\n');
+
+            out.push(chars.vertical);
+            for j in 0..ncols {
+                let contents = if self.blocks[[i, j]] {
+                    String::new()
+                } else {
+                    square_str(&self.grid[[i, j]]).to_string()
+                };
+                out.push_str(&self.render_cell(i, j, &contents, cell_width, &chars));
+                out.push(chars.vertical);
+            }
+            out.push('\n');
+
+            if i + 1 < nrows {
+                out.push_str(&border(chars.mid_left, chars.cross, chars.mid_right));
+            }
+        }
+        out.push_str(&border(chars.bottom_left, chars.bottom_mid, chars.bottom_right));
+        out
+    }
+
+    /// Pads `text` to `cell_width` columns, or fills the whole cell with the style's
+    /// block character if `(i, j)` is a block.
+    fn render_cell(&self, i: usize, j: usize, text: &str, cell_width: usize, chars: &BoxChars) -> String {
+        if self.blocks[[i, j]] {
+            return chars.block_fill.to_string().repeat(cell_width);
+        }
+        let padding = cell_width.saturating_sub(UnicodeWidthStr::width(text));
+        format!("{text}{}", " ".repeat(padding))
+    }
+}
+
+/// Box-drawing character set used by [`Puzzle::render_grid`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GridStyle {
+    /// `┌─┬─┐` single-line Unicode box-drawing characters.
+    Light,
+    /// `+-+` ASCII-only fallback for terminals without Unicode box-drawing support.
+    Ascii,
+}
+
+/// The individual glyphs making up a [`GridStyle`]; kept separate from the enum so
+/// `render_grid` can work with a plain set of characters regardless of which style
+/// was requested.
+struct BoxChars {
+    horizontal: char,
+    vertical: char,
+    top_left: char,
+    top_mid: char,
+    top_right: char,
+    mid_left: char,
+    cross: char,
+    mid_right: char,
+    bottom_left: char,
+    bottom_mid: char,
+    bottom_right: char,
+    block_fill: char,
+}
+
+impl GridStyle {
+    fn box_chars(self) -> BoxChars {
+        match self {
+            GridStyle::Light => BoxChars {
+                horizontal: '─',
+                vertical: '│',
+                top_left: '┌',
+                top_mid: '┬',
+                top_right: '┐',
+                mid_left: '├',
+                cross: '┼',
+                mid_right: '┤',
+                bottom_left: '└',
+                bottom_mid: '┴',
+                bottom_right: '┘',
+                block_fill: '█',
+            },
+            GridStyle::Ascii => BoxChars {
+                horizontal: '-',
+                vertical: '|',
+                top_left: '+',
+                top_mid: '+',
+                top_right: '+',
+                mid_left: '+',
+                cross: '+',
+                mid_right: '+',
+                bottom_left: '+',
+                bottom_mid: '+',
+                bottom_right: '+',
+                block_fill: '#',
+            },
+        }
+    }
+}
+
+fn square_str(square: &Square) -> &str {
+    square.as_deref().unwrap_or("_")
 }
 
 fn fmt_squares<'a, I>(f: &mut fmt::Formatter<'_>, squares: I, indent: Option<&str>) -> fmt::Result
@@ -228,7 +625,7 @@ where
         write!(f, "{indent_str}")?;
     }
     for square in squares {
-        write!(f, "{}", square.unwrap_or('.'))?;
+        write!(f, "{}", square_str(square))?;
     }
     writeln!(f)
 }
@@ -236,8 +633,16 @@ where
 impl fmt::Display for Puzzle {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "Grid{{")?;
-        for row in self.grid.rows() {
-            fmt_squares(f, row.iter(), Some("  "))?;
+        for (row, block_row) in self.grid.rows().into_iter().zip(self.blocks.rows()) {
+            write!(f, "  ")?;
+            for (square, is_block) in row.iter().zip(block_row.iter()) {
+                if *is_block {
+                    write!(f, ".")?;
+                } else {
+                    write!(f, "{}", square_str(square))?;
+                }
+            }
+            writeln!(f)?;
         }
         writeln!(f)?;
 
@@ -262,7 +667,7 @@ impl fmt::Display for Puzzle {
 
 #[cfg(test)]
 mod tests {
-    use super::Puzzle;
+    use super::{Crossing, Direction, GridStyle, Puzzle};
 
     #[test]
     fn puzzle_creation_errors() {
@@ -283,15 +688,30 @@ DE.FGH\
             Puzzle::parse(too_many_chars).is_err(),
             "too few characters in the second row"
         );
+    }
 
-        let multi_character_grapheme = "\
-.aÃêBC.
+    #[test]
+    fn grapheme_cluster_squares() {
+        // "e" followed by a combining acute accent is a single grapheme cluster
+        let with_combining_accent = "\
+.e\u{0301}BC.
 DE.FG\
 ";
-        assert!(
-            Puzzle::parse(multi_character_grapheme).is_err(),
-            "can't currently handle a multi-character grapheme cluster"
-        );
+        let puzzle = Puzzle::parse(with_combining_accent).unwrap();
+        assert_eq!(String::from(puzzle.access(0)), "e\u{0301}BC");
+    }
+
+    #[test]
+    fn blank_squares_are_unfilled() {
+        let partially_filled = "\
+AB
+C \
+";
+        let puzzle = Puzzle::parse(partially_filled).unwrap();
+        // the down slot in the second column is "B" over a not-yet-solved square
+        let down_col1 = puzzle.access(3);
+        assert_eq!(down_col1.get(0), Some("B"));
+        assert_eq!(down_col1.get(1), None);
     }
 
     #[test]
@@ -343,4 +763,123 @@ TROUT
             assert_eq!(String::from(modified.access(i)), *val);
         }
     }
+
+    #[test]
+    fn cell_numbering() {
+        let crossword_str = "\
+AB
+CD\
+";
+        let puzzle = Puzzle::parse(crossword_str).unwrap();
+
+        // "AB" and "CD" are the acrosses, "AC" and "BD" the downs
+        assert_eq!(puzzle.slot_number(0), (1, Direction::Across)); // AB
+        assert_eq!(puzzle.slot_number(1), (3, Direction::Across)); // CD
+        assert_eq!(puzzle.slot_number(2), (1, Direction::Down)); // AC
+        assert_eq!(puzzle.slot_number(3), (2, Direction::Down)); // BD
+
+        let clue_list: Vec<(usize, Direction, String)> = puzzle
+            .numbered_slots()
+            .map(|(number, direction, slot)| (number, direction, String::from(slot)))
+            .collect();
+        assert_eq!(
+            clue_list,
+            vec![
+                (1, Direction::Across, "AB".to_string()),
+                (1, Direction::Down, "AC".to_string()),
+                (2, Direction::Down, "BD".to_string()),
+                (3, Direction::Across, "CD".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn slot_crossings() {
+        let crossword_str = "\
+AB
+CD\
+";
+        let puzzle = Puzzle::parse(crossword_str).unwrap();
+
+        // slots 0,1 are the acrosses (AB, CD); slots 2,3 are the downs (AC, BD)
+        assert_eq!(
+            puzzle.crossings(),
+            vec![
+                Crossing {
+                    across: 0,
+                    down: 2,
+                    across_offset: 0,
+                    down_offset: 0,
+                },
+                Crossing {
+                    across: 0,
+                    down: 3,
+                    across_offset: 1,
+                    down_offset: 0,
+                },
+                Crossing {
+                    across: 1,
+                    down: 2,
+                    across_offset: 0,
+                    down_offset: 1,
+                },
+                Crossing {
+                    across: 1,
+                    down: 3,
+                    across_offset: 1,
+                    down_offset: 1,
+                },
+            ]
+        );
+
+        // "AB" (slot 0) crosses both downs
+        assert_eq!(puzzle.crossings_of(0).len(), 2);
+        assert!(puzzle.is_consistent());
+    }
+
+    #[test]
+    fn render_grid_pads_and_labels_cells() {
+        let crossword_str = "\
+AB
+CD\
+";
+        let puzzle = Puzzle::parse(crossword_str).unwrap();
+
+        assert_eq!(
+            puzzle.render_grid(GridStyle::Ascii),
+            "\
++-+-+
+|1|2|
+|A|B|
++-+-+
+|3| |
+|C|D|
++-+-+
+"
+        );
+    }
+
+    #[test]
+    fn render_grid_widens_cells_for_double_width_graphemes() {
+        // a CJK character displays as 2 columns wide, so every cell should widen to
+        // fit it, even the ones holding a plain ASCII letter
+        let crossword_str = "\
+A\u{56FD}
+CD\
+";
+        let puzzle = Puzzle::parse(crossword_str).unwrap();
+
+        assert_eq!(
+            puzzle.render_grid(GridStyle::Ascii),
+            "\
++--+--+
+|1 |2 |
+|A |\u{56FD}|
++--+--+
+|3 |  |
+|C |D |
++--+--+
+"
+        );
+    }
 }
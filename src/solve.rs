@@ -0,0 +1,335 @@
+//! Backtracking autofill solver tying [`Puzzle`] and [`Lexicon`] together.
+
+use crate::lexicon::Lexicon;
+use crate::puzzle::Puzzle;
+use std::collections::{HashSet, VecDeque};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Diagnostics from a [`solve`] attempt.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SolveStats {
+    /// Number of candidate words tried across the whole search.
+    pub nodes: usize,
+    /// Number of times a candidate was undone because it led to a dead end.
+    pub backtracks: usize,
+}
+
+/// Fills every slot of `puzzle` with a word from `lexicon`, such that every slot is a
+/// dictionary word and every crossing square agrees.
+///
+/// Uses constraint-propagation backtracking: the unfilled slot with the fewest
+/// remaining candidates is assigned next (minimum-remaining-values heuristic), and
+/// after placing a word, every slot that crosses it is re-checked to confirm it still
+/// has at least one candidate before recursing; on a dead end, the word is undone and
+/// the next candidate is tried. Returns `None` if no solution exists.
+pub fn solve(puzzle: &Puzzle, lexicon: &Lexicon) -> (Option<Puzzle>, SolveStats) {
+    let mut working = puzzle.clone();
+    let mut stats = SolveStats::default();
+    if !filled_slots_are_valid(&working, lexicon) {
+        return (None, stats);
+    }
+    let solved = solve_inner(&mut working, lexicon, &mut stats);
+    (solved.then_some(working), stats)
+}
+
+fn is_filled(puzzle: &Puzzle, slot_i: usize) -> bool {
+    let slot = puzzle.access(slot_i);
+    (0..slot.len()).all(|j| slot.get(j).is_some())
+}
+
+/// Whether every slot that's already filled (e.g. by the caller, before search ever
+/// touches it) is a dictionary word.
+///
+/// The search only ever assigns slots `is_filled` says are still empty, so a
+/// pre-filled slot that isn't in `lexicon` would otherwise never be checked against
+/// it at all.
+fn filled_slots_are_valid(puzzle: &Puzzle, lexicon: &Lexicon) -> bool {
+    (0..puzzle.nslots())
+        .filter(|&i| is_filled(puzzle, i))
+        .all(|i| !lexicon.possible_answer_indices(&puzzle.access(i)).is_empty())
+}
+
+/// The unfilled slot with the fewest remaining candidates, or `None` once every slot
+/// is filled.
+fn next_slot(puzzle: &Puzzle, lexicon: &Lexicon) -> Option<usize> {
+    (0..puzzle.nslots())
+        .filter(|&i| !is_filled(puzzle, i))
+        .min_by_key(|&i| lexicon.possible_answer_indices(&puzzle.access(i)).len())
+}
+
+/// Whether every slot crossing `slot_i` still has at least one candidate word.
+fn crossings_have_candidates(puzzle: &Puzzle, lexicon: &Lexicon, slot_i: usize) -> bool {
+    (0..puzzle.access(slot_i).len()).all(|offset| {
+        let (crossing_i, _) = puzzle.crossing(slot_i, offset);
+        !lexicon
+            .possible_answer_indices(&puzzle.access(crossing_i))
+            .is_empty()
+    })
+}
+
+fn solve_inner(puzzle: &mut Puzzle, lexicon: &Lexicon, stats: &mut SolveStats) -> bool {
+    let Some(slot_i) = next_slot(puzzle, lexicon) else {
+        return true; // every slot is already filled
+    };
+
+    let words = lexicon.words_by_length(puzzle.access(slot_i).len());
+    let candidates = lexicon.possible_answer_indices(&puzzle.access(slot_i));
+
+    for candidate_i in candidates {
+        stats.nodes += 1;
+        let saved = puzzle.slot_contents(slot_i);
+        puzzle.fill_slot_mut(slot_i, &words[candidate_i]);
+
+        if crossings_have_candidates(puzzle, lexicon, slot_i) && solve_inner(puzzle, lexicon, stats) {
+            return true;
+        }
+
+        puzzle.restore_slot_mut(slot_i, &saved);
+        stats.backtracks += 1;
+    }
+    false
+}
+
+/// Like [`solve`], but maintains a candidate-word domain for every slot and enforces
+/// full arc consistency (propagating eliminations transitively across
+/// [`Puzzle::crossings_of`]) instead of only re-checking the slots adjacent to the one
+/// just filled.
+///
+/// `solve`'s forward checking can miss a dead end that's two or more crossings away
+/// from the slot just filled, and only discovers it by recursing into it; this variant
+/// prunes those branches before guessing, at the cost of the extra bookkeeping needed
+/// to track and restore every slot's domain at each search node. Returns `None` if no
+/// solution exists.
+pub fn solve_arc_consistent(puzzle: &Puzzle, lexicon: &Lexicon) -> (Option<Puzzle>, SolveStats) {
+    let mut working = puzzle.clone();
+    let mut stats = SolveStats::default();
+    if !filled_slots_are_valid(&working, lexicon) {
+        return (None, stats);
+    }
+    let mut domains: Vec<Vec<usize>> = (0..working.nslots())
+        .map(|i| lexicon.possible_answer_indices(&working.access(i)))
+        .collect();
+
+    if !ac3(&working, lexicon, &mut domains, initial_arcs(&working)) {
+        return (None, stats);
+    }
+
+    let solved = search_arc_consistent(&mut working, lexicon, &mut domains, &mut stats);
+    (solved.then_some(working), stats)
+}
+
+/// The grapheme cluster at `offset` within `word`.
+fn letter_at(word: &str, offset: usize) -> &str {
+    word.graphemes(true)
+        .nth(offset)
+        .expect("offset is within the word's length")
+}
+
+/// One arc per ordered pair of slots that cross, seeded from every crossing in the grid.
+fn initial_arcs(puzzle: &Puzzle) -> VecDeque<(usize, usize)> {
+    puzzle
+        .crossings()
+        .iter()
+        .flat_map(|crossing| {
+            [
+                (crossing.across, crossing.down),
+                (crossing.down, crossing.across),
+            ]
+        })
+        .collect()
+}
+
+/// The arcs `(neighbor, slot_i)` for every slot crossing `slot_i`, run after `slot_i`'s
+/// domain changes so its neighbors get re-revised against it.
+fn arcs_into_neighbors_of(puzzle: &Puzzle, slot_i: usize) -> VecDeque<(usize, usize)> {
+    puzzle
+        .crossings_of(slot_i)
+        .iter()
+        .map(|crossing| {
+            let neighbor = if crossing.across == slot_i {
+                crossing.down
+            } else {
+                crossing.across
+            };
+            (neighbor, slot_i)
+        })
+        .collect()
+}
+
+/// The `(offset_in_a, offset_in_b)` position of the shared square where slots `a` and
+/// `b` cross.
+fn crossing_offsets(puzzle: &Puzzle, a: usize, b: usize) -> (usize, usize) {
+    puzzle
+        .crossings_of(a)
+        .iter()
+        .find_map(|crossing| {
+            if crossing.across == a && crossing.down == b {
+                Some((crossing.across_offset, crossing.down_offset))
+            } else if crossing.down == a && crossing.across == b {
+                Some((crossing.down_offset, crossing.across_offset))
+            } else {
+                None
+            }
+        })
+        .expect("a and b must cross")
+}
+
+/// Removes candidates from `domains[a]` that have no supporting candidate in
+/// `domains[b]` at the square where they cross. Returns whether `domains[a]` shrank.
+fn revise(puzzle: &Puzzle, lexicon: &Lexicon, domains: &mut [Vec<usize>], a: usize, b: usize) -> bool {
+    let (offset_a, offset_b) = crossing_offsets(puzzle, a, b);
+    let words_a = lexicon.words_by_length(puzzle.access(a).len());
+    let words_b = lexicon.words_by_length(puzzle.access(b).len());
+
+    let letters_b: HashSet<&str> = domains[b]
+        .iter()
+        .map(|&i| letter_at(&words_b[i], offset_b))
+        .collect();
+
+    let before = domains[a].len();
+    domains[a].retain(|&i| letters_b.contains(letter_at(&words_a[i], offset_a)));
+    domains[a].len() != before
+}
+
+/// Propagates `queue` until it's empty or some domain is emptied, in which case the
+/// grid being searched is inconsistent and `false` is returned.
+fn ac3(
+    puzzle: &Puzzle,
+    lexicon: &Lexicon,
+    domains: &mut [Vec<usize>],
+    mut queue: VecDeque<(usize, usize)>,
+) -> bool {
+    while let Some((a, b)) = queue.pop_front() {
+        if revise(puzzle, lexicon, domains, a, b) {
+            if domains[a].is_empty() {
+                return false;
+            }
+            for (neighbor, _) in arcs_into_neighbors_of(puzzle, a) {
+                if neighbor != b {
+                    queue.push_back((neighbor, a));
+                }
+            }
+        }
+    }
+    true
+}
+
+fn search_arc_consistent(
+    puzzle: &mut Puzzle,
+    lexicon: &Lexicon,
+    domains: &mut Vec<Vec<usize>>,
+    stats: &mut SolveStats,
+) -> bool {
+    let Some(slot_i) = (0..puzzle.nslots())
+        .filter(|&i| !is_filled(puzzle, i))
+        .min_by_key(|&i| domains[i].len())
+    else {
+        return true; // every slot is already filled
+    };
+
+    let words = lexicon.words_by_length(puzzle.access(slot_i).len());
+    for candidate_i in domains[slot_i].clone() {
+        stats.nodes += 1;
+        let saved_slot = puzzle.slot_contents(slot_i);
+        let saved_domains = domains.clone();
+
+        puzzle.fill_slot_mut(slot_i, &words[candidate_i]);
+        domains[slot_i] = vec![candidate_i];
+
+        if ac3(puzzle, lexicon, domains, arcs_into_neighbors_of(puzzle, slot_i))
+            && search_arc_consistent(puzzle, lexicon, domains, stats)
+        {
+            return true;
+        }
+
+        *domains = saved_domains;
+        puzzle.restore_slot_mut(slot_i, &saved_slot);
+        stats.backtracks += 1;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_a_trivial_grid() {
+        // a single row, so every column also forms a length-1 down slot; include
+        // single-letter "words" so those crossings are satisfiable too
+        let puzzle = Puzzle::parse("   ").unwrap();
+        let lexicon = Lexicon::from_words(
+            ["cat", "c", "a", "t"].into_iter().map(String::from).collect(),
+        );
+
+        let (solved, stats) = solve(&puzzle, &lexicon);
+        let solved = solved.expect("a single-row puzzle with one matching word should solve");
+        assert_eq!(String::from(solved.access(0)), "CAT");
+        assert_eq!(stats.backtracks, 0);
+    }
+
+    #[test]
+    fn reports_infeasibility() {
+        // the lexicon only has a 3-letter word, so a 2-letter slot can't be filled
+        let puzzle = Puzzle::parse("  ").unwrap();
+        let lexicon = Lexicon::from_words(vec!["dog".to_string()]);
+
+        let (solved, _stats) = solve(&puzzle, &lexicon);
+        assert!(solved.is_none());
+    }
+
+    #[test]
+    fn rejects_a_prefilled_slot_that_is_not_a_dictionary_word() {
+        // the grid arrives already fully filled with "CAT", but the lexicon's only
+        // 3-letter word is "dog" and it has no 1-letter words for the per-column down
+        // slots either, so nothing in this grid is a valid answer
+        let puzzle = Puzzle::parse("CAT").unwrap();
+        let lexicon = Lexicon::from_words(vec!["dog".to_string()]);
+
+        let (solved, _stats) = solve(&puzzle, &lexicon);
+        assert!(solved.is_none());
+    }
+
+    #[test]
+    fn arc_consistent_solves_a_trivial_grid() {
+        let puzzle = Puzzle::parse("   ").unwrap();
+        let lexicon = Lexicon::from_words(
+            ["cat", "c", "a", "t"].into_iter().map(String::from).collect(),
+        );
+
+        let (solved, _stats) = solve_arc_consistent(&puzzle, &lexicon);
+        let solved = solved.expect("a single-row puzzle with one matching word should solve");
+        assert_eq!(String::from(solved.access(0)), "CAT");
+    }
+
+    #[test]
+    fn arc_consistent_reports_infeasibility() {
+        let puzzle = Puzzle::parse("  ").unwrap();
+        let lexicon = Lexicon::from_words(vec!["dog".to_string()]);
+
+        let (solved, _stats) = solve_arc_consistent(&puzzle, &lexicon);
+        assert!(solved.is_none());
+    }
+
+    #[test]
+    fn arc_consistent_rejects_a_prefilled_slot_that_is_not_a_dictionary_word() {
+        let puzzle = Puzzle::parse("CAT").unwrap();
+        let lexicon = Lexicon::from_words(vec!["dog".to_string()]);
+
+        let (solved, _stats) = solve_arc_consistent(&puzzle, &lexicon);
+        assert!(solved.is_none());
+    }
+
+    #[test]
+    fn arc_consistent_solves_a_grid_with_crossings() {
+        // a blank 2x2 grid: two across slots crossing two down slots
+        let puzzle = Puzzle::parse("  \n  ").unwrap();
+        let lexicon = Lexicon::from_words(
+            ["at", "ox", "ao", "tx"].into_iter().map(String::from).collect(),
+        );
+
+        let (solved, _stats) = solve_arc_consistent(&puzzle, &lexicon);
+        let solved = solved.expect("a consistent assignment exists");
+        assert!(solved.is_consistent());
+    }
+}